@@ -0,0 +1,384 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use xz2::stream::{Check, Filters, LzmaOptions, Stream};
+use xz2::write::XzEncoder;
+use xz2::read::XzDecoder;
+
+/// Large window gives much better ratios on text-heavy `.plib`/`.aoe` files;
+/// callers on memory-constrained machines can pass `lowMemory` instead.
+const DICT_SIZE_DEFAULT: u32 = 64 * 1024 * 1024;
+const DICT_SIZE_LOW_MEMORY: u32 = 8 * 1024 * 1024;
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp", "tiff", "tif"];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+  path: String,
+  ok: bool,
+  error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportManifest {
+  archive_path: String,
+  entries: Vec<ManifestEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportManifest {
+  entries: Vec<ManifestEntry>,
+  collisions: Vec<String>,
+}
+
+#[tauri::command]
+pub fn export_library(
+  paths: Vec<String>,
+  dest: String,
+  low_memory: Option<bool>,
+) -> Result<ExportManifest, String> {
+  if paths.is_empty() {
+    return Err("No paths were provided to export.".into());
+  }
+
+  let mut sources = Vec::new();
+  let mut seen_sources = HashSet::new();
+  for path in &paths {
+    let source = PathBuf::from(path);
+    if seen_sources.insert(source.clone()) {
+      sources.push(source.clone());
+    }
+    for image in referenced_image_paths(&source) {
+      if seen_sources.insert(image.clone()) {
+        sources.push(image);
+      }
+    }
+  }
+
+  let dest_path = PathBuf::from(&dest);
+  let file = File::create(&dest_path).map_err(|err| err.to_string())?;
+  let encoder = xz_encoder(file, low_memory.unwrap_or(false))?;
+  let mut builder = tar::Builder::new(encoder);
+
+  let mut used_names = HashSet::new();
+  let mut entries = Vec::with_capacity(sources.len());
+  for source in sources {
+    let path = source.to_string_lossy().to_string();
+    let result = source
+      .file_name()
+      .ok_or_else(|| "Path has no file name.".to_string())
+      .and_then(|name| {
+        let archive_name = unique_archive_name(name, &mut used_names);
+        builder
+          .append_path_with_name(&source, &archive_name)
+          .map_err(|err| err.to_string())
+      });
+
+    entries.push(match result {
+      Ok(()) => ManifestEntry {
+        path,
+        ok: true,
+        error: None,
+      },
+      Err(err) => ManifestEntry {
+        path,
+        ok: false,
+        error: Some(err),
+      },
+    });
+  }
+
+  let encoder = builder.into_inner().map_err(|err| err.to_string())?;
+  encoder.finish().map_err(|err| err.to_string())?;
+
+  Ok(ExportManifest {
+    archive_path: dest,
+    entries,
+  })
+}
+
+/// Best-effort scan for image paths referenced from within a `.plib`/`.aoe`
+/// file's text content, resolved relative to the file's own directory, so
+/// an export carries along the images a prompt library actually points at.
+fn referenced_image_paths(source: &Path) -> Vec<PathBuf> {
+  let Ok(contents) = std::fs::read_to_string(source) else {
+    return Vec::new();
+  };
+  let base_dir = source.parent().unwrap_or_else(|| Path::new("."));
+
+  let mut seen = HashSet::new();
+  let mut images = Vec::new();
+
+  for token in contents.split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ',' | '(' | ')' | '[' | ']')) {
+    let token = token.trim();
+    if token.is_empty() {
+      continue;
+    }
+
+    let is_image = Path::new(token)
+      .extension()
+      .and_then(|ext| ext.to_str())
+      .map(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+      .unwrap_or(false);
+    if !is_image {
+      continue;
+    }
+
+    let candidate = if Path::new(token).is_absolute() {
+      PathBuf::from(token)
+    } else {
+      base_dir.join(token)
+    };
+
+    if candidate.is_file() && seen.insert(candidate.clone()) {
+      images.push(candidate);
+    }
+  }
+
+  images
+}
+
+/// Renames an archive entry's basename on collision instead of letting a
+/// later `append_path_with_name` silently overwrite an earlier one.
+fn unique_archive_name(name: &OsStr, used: &mut HashSet<String>) -> String {
+  let name = name.to_string_lossy().to_string();
+  if used.insert(name.clone()) {
+    return name;
+  }
+
+  let stem = Path::new(&name)
+    .file_stem()
+    .and_then(|stem| stem.to_str())
+    .unwrap_or(&name)
+    .to_string();
+  let extension = Path::new(&name)
+    .extension()
+    .and_then(|ext| ext.to_str())
+    .map(str::to_string);
+
+  let mut counter = 2;
+  loop {
+    let candidate = match &extension {
+      Some(extension) => format!("{stem}-{counter}.{extension}"),
+      None => format!("{stem}-{counter}"),
+    };
+    if used.insert(candidate.clone()) {
+      return candidate;
+    }
+    counter += 1;
+  }
+}
+
+#[tauri::command]
+pub fn import_library(archive: String, dest_dir: String) -> Result<ImportManifest, String> {
+  let archive_path = Path::new(&archive);
+  if !archive_path.exists() {
+    return Err(format!("Archive does not exist: {archive}"));
+  }
+
+  let dest_dir = PathBuf::from(dest_dir);
+  std::fs::create_dir_all(&dest_dir).map_err(|err| err.to_string())?;
+
+  let file = File::open(archive_path).map_err(|err| err.to_string())?;
+  let decoder = XzDecoder::new(file);
+  let mut tar = tar::Archive::new(decoder);
+
+  let mut entries = Vec::new();
+  let mut collisions = Vec::new();
+
+  for entry in tar.entries().map_err(|err| err.to_string())? {
+    let mut entry = match entry {
+      Ok(entry) => entry,
+      Err(err) => {
+        entries.push(ManifestEntry {
+          path: "<unknown>".into(),
+          ok: false,
+          error: Some(err.to_string()),
+        });
+        continue;
+      }
+    };
+
+    let raw_path = match entry.path() {
+      Ok(path) => path.to_path_buf(),
+      Err(err) => {
+        entries.push(ManifestEntry {
+          path: "<unknown>".into(),
+          ok: false,
+          error: Some(err.to_string()),
+        });
+        continue;
+      }
+    };
+
+    let relative_path = match sanitize_relative_path(&raw_path) {
+      Ok(path) => path,
+      Err(err) => {
+        entries.push(ManifestEntry {
+          path: raw_path.to_string_lossy().to_string(),
+          ok: false,
+          error: Some(err),
+        });
+        continue;
+      }
+    };
+    let target_path = dest_dir.join(&relative_path);
+    let path_string = relative_path.to_string_lossy().to_string();
+
+    if target_path.exists() {
+      collisions.push(path_string.clone());
+    }
+
+    match entry.unpack(&target_path) {
+      Ok(_) => entries.push(ManifestEntry {
+        path: path_string,
+        ok: true,
+        error: None,
+      }),
+      Err(err) => entries.push(ManifestEntry {
+        path: path_string,
+        ok: false,
+        error: Some(err.to_string()),
+      }),
+    }
+  }
+
+  Ok(ImportManifest {
+    entries,
+    collisions,
+  })
+}
+
+/// Rejects archive entries that would escape `dest_dir` (absolute paths or
+/// `..` components), since `Entry::unpack(dst)` — unlike `Archive::unpack`
+/// — does not sanitize the destination path itself.
+fn sanitize_relative_path(path: &Path) -> Result<PathBuf, String> {
+  use std::path::Component;
+
+  let mut sanitized = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::Normal(part) => sanitized.push(part),
+      Component::CurDir => {}
+      Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+        return Err(format!(
+          "Archive entry escapes the destination directory: {}",
+          path.display()
+        ));
+      }
+    }
+  }
+
+  if sanitized.as_os_str().is_empty() {
+    return Err(format!("Archive entry has an empty path: {}", path.display()));
+  }
+
+  Ok(sanitized)
+}
+
+fn xz_encoder(file: File, low_memory: bool) -> Result<XzEncoder<File>, String> {
+  let dict_size = if low_memory {
+    DICT_SIZE_LOW_MEMORY
+  } else {
+    DICT_SIZE_DEFAULT
+  };
+
+  let mut options = LzmaOptions::new_preset(6).map_err(|err| err.to_string())?;
+  options.dict_size(dict_size);
+
+  let mut filters = Filters::new();
+  filters.lzma2(&options);
+  let stream =
+    Stream::new_stream_encoder(&filters, Check::Crc64).map_err(|err| err.to_string())?;
+  Ok(XzEncoder::new_stream(file, stream))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sanitize_relative_path_allows_normal_paths() {
+    assert_eq!(
+      sanitize_relative_path(Path::new("images/pic.png")).unwrap(),
+      PathBuf::from("images/pic.png")
+    );
+  }
+
+  #[test]
+  fn sanitize_relative_path_rejects_parent_dir_components() {
+    assert!(sanitize_relative_path(Path::new("../../etc/passwd")).is_err());
+    assert!(sanitize_relative_path(Path::new("images/../../evil.txt")).is_err());
+  }
+
+  #[test]
+  fn sanitize_relative_path_rejects_absolute_paths() {
+    assert!(sanitize_relative_path(Path::new("/etc/passwd")).is_err());
+  }
+
+  #[test]
+  fn unique_archive_name_renames_on_collision() {
+    let mut used = HashSet::new();
+    assert_eq!(
+      unique_archive_name(OsStr::new("prompt.plib"), &mut used),
+      "prompt.plib"
+    );
+    assert_eq!(
+      unique_archive_name(OsStr::new("prompt.plib"), &mut used),
+      "prompt-2.plib"
+    );
+    assert_eq!(
+      unique_archive_name(OsStr::new("prompt.plib"), &mut used),
+      "prompt-3.plib"
+    );
+  }
+
+  fn write_archive_with_entry(archive_path: &Path, entry_name: &str, data: &[u8]) {
+    let file = File::create(archive_path).unwrap();
+    let stream = Stream::new_easy_encoder(0, Check::Crc64).unwrap();
+    let encoder = XzEncoder::new_stream(file, stream);
+    let mut builder = tar::Builder::new(encoder);
+
+    // `append_data`/`Header::set_path` both refuse `..` components, so the
+    // malicious name is written directly into the raw header bytes to
+    // reproduce what a hand-crafted hostile archive would contain.
+    let mut header = tar::Header::new_gnu();
+    let name_bytes = entry_name.as_bytes();
+    header.as_mut_bytes()[..name_bytes.len()].copy_from_slice(name_bytes);
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append(&header, data).unwrap();
+
+    let encoder = builder.into_inner().unwrap();
+    encoder.finish().unwrap();
+  }
+
+  #[test]
+  fn import_library_rejects_path_traversal_entries() {
+    let tmp_dir = std::env::temp_dir().join(format!(
+      "promptlibrary-explorer-import-test-{}",
+      std::process::id()
+    ));
+    std::fs::create_dir_all(&tmp_dir).unwrap();
+    let archive_path = tmp_dir.join("malicious.tar.xz");
+    write_archive_with_entry(&archive_path, "../../escaped.txt", b"malicious payload");
+
+    let dest_dir = tmp_dir.join("dest");
+    let manifest = import_library(
+      archive_path.to_string_lossy().to_string(),
+      dest_dir.to_string_lossy().to_string(),
+    )
+    .unwrap();
+
+    assert!(manifest.entries.iter().all(|entry| !entry.ok));
+    assert!(!dest_dir.join("../../escaped.txt").exists());
+
+    let _ = std::fs::remove_dir_all(&tmp_dir);
+  }
+}
@@ -0,0 +1,65 @@
+use crate::FileMetadataResult;
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchItemResult<T: Serialize> {
+  path: String,
+  ok: bool,
+  error: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  data: Option<T>,
+}
+
+impl<T: Serialize> BatchItemResult<T> {
+  fn ok(path: String, data: T) -> Self {
+    Self {
+      path,
+      ok: true,
+      error: None,
+      data: Some(data),
+    }
+  }
+
+  fn err(path: String, error: String) -> Self {
+    Self {
+      path,
+      ok: false,
+      error: Some(error),
+      data: None,
+    }
+  }
+}
+
+#[tauri::command]
+pub fn move_to_trash_many(paths: Vec<String>) -> Vec<BatchItemResult<()>> {
+  paths
+    .into_iter()
+    .map(|path| match crate::move_to_trash(path.clone()) {
+      Ok(()) => BatchItemResult::ok(path, ()),
+      Err(err) => BatchItemResult::err(path, err),
+    })
+    .collect()
+}
+
+#[tauri::command]
+pub fn get_file_metadata_many(paths: Vec<String>) -> Vec<BatchItemResult<FileMetadataResult>> {
+  paths
+    .into_iter()
+    .map(|path| match crate::get_file_metadata(path.clone()) {
+      Ok(metadata) => BatchItemResult::ok(path, metadata),
+      Err(err) => BatchItemResult::err(path, err),
+    })
+    .collect()
+}
+
+#[tauri::command]
+pub fn reveal_in_file_manager_many(paths: Vec<String>) -> Vec<BatchItemResult<()>> {
+  paths
+    .into_iter()
+    .map(|path| match crate::reveal_in_file_manager(path.clone()) {
+      Ok(()) => BatchItemResult::ok(path, ()),
+      Err(err) => BatchItemResult::err(path, err),
+    })
+    .collect()
+}
@@ -1,5 +1,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
+mod archive;
+mod batch;
+mod open_with;
+mod spawn;
+mod thumbnail;
+
 use image::GenericImageView;
 use serde::Serialize;
 use std::{
@@ -11,12 +17,33 @@ use std::{
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-struct FileMetadataResult {
+pub(crate) struct FileMetadataResult {
   file_name: String,
   file_type: String,
   width: Option<u32>,
   height: Option<u32>,
   modified_ms: Option<u64>,
+  created_ms: Option<u64>,
+  accessed_ms: Option<u64>,
+  size_bytes: u64,
+  is_directory: bool,
+  is_file: bool,
+  is_symlink: bool,
+  permissions: Option<String>,
+  directory_item_count: Option<u64>,
+}
+
+fn system_time_to_ms(time: SystemTime) -> u64 {
+  let millis = time
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_millis())
+    .unwrap_or(0);
+
+  if millis > u64::MAX as u128 {
+    u64::MAX
+  } else {
+    millis as u64
+  }
 }
 
 fn describe_file_type(extension: &str) -> String {
@@ -35,7 +62,7 @@ fn describe_file_type(extension: &str) -> String {
 }
 
 #[tauri::command]
-fn get_file_metadata(target_path: String) -> Result<FileMetadataResult, String> {
+pub(crate) fn get_file_metadata(target_path: String) -> Result<FileMetadataResult, String> {
   let trimmed = target_path.trim();
   if trimmed.is_empty() {
     return Err("Target path was empty.".into());
@@ -47,6 +74,9 @@ fn get_file_metadata(target_path: String) -> Result<FileMetadataResult, String>
   }
 
   let metadata = fs::metadata(path).map_err(|err| err.to_string())?;
+  let is_symlink = fs::symlink_metadata(path)
+    .map(|meta| meta.is_symlink())
+    .unwrap_or(false);
   let file_name = path
     .file_name()
     .and_then(|name| name.to_str())
@@ -59,18 +89,9 @@ fn get_file_metadata(target_path: String) -> Result<FileMetadataResult, String>
     .to_lowercase();
   let file_type = describe_file_type(&extension);
 
-  let modified_ms = metadata
-    .modified()
-    .ok()
-    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
-    .map(|duration| {
-      let millis = duration.as_millis();
-      if millis > u64::MAX as u128 {
-        u64::MAX
-      } else {
-        millis as u64
-      }
-    });
+  let modified_ms = metadata.modified().ok().map(system_time_to_ms);
+  let created_ms = metadata.created().ok().map(system_time_to_ms);
+  let accessed_ms = metadata.accessed().ok().map(system_time_to_ms);
 
   let (width, height) = match extension.as_str() {
     "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "tiff" | "tif" => {
@@ -82,24 +103,67 @@ fn get_file_metadata(target_path: String) -> Result<FileMetadataResult, String>
     _ => (None, None),
   };
 
+  let is_directory = metadata.is_dir();
+  let directory_item_count = if is_directory {
+    fs::read_dir(path).ok().map(|entries| entries.count() as u64)
+  } else {
+    None
+  };
+
   Ok(FileMetadataResult {
     file_name,
     file_type,
     width,
     height,
     modified_ms,
+    created_ms,
+    accessed_ms,
+    size_bytes: metadata.len(),
+    is_directory,
+    is_file: metadata.is_file(),
+    is_symlink,
+    permissions: describe_permissions(&metadata),
+    directory_item_count,
   })
 }
 
+#[cfg(unix)]
+fn describe_permissions(metadata: &fs::Metadata) -> Option<String> {
+  use std::os::unix::fs::PermissionsExt;
+
+  let mode = metadata.permissions().mode();
+  let owner_rwx = [
+    (mode & 0o400 != 0, 'r'),
+    (mode & 0o200 != 0, 'w'),
+    (mode & 0o100 != 0, 'x'),
+  ]
+  .iter()
+  .map(|(set, letter)| if *set { *letter } else { '-' })
+  .collect::<String>();
+
+  Some(format!("{:o} ({owner_rwx})", mode & 0o777))
+}
+
+#[cfg(not(unix))]
+fn describe_permissions(_metadata: &fs::Metadata) -> Option<String> {
+  None
+}
+
+/// Decodes an in-memory image and converts it to RGBA8, the pixel format
+/// both the clipboard and the thumbnail pipeline need.
+pub(crate) fn decode_to_rgba(bytes: &[u8]) -> Result<(image::RgbaImage, u32, u32), String> {
+  let image = image::load_from_memory(bytes).map_err(|err| err.to_string())?;
+  let (width, height) = image.dimensions();
+  Ok((image.to_rgba8(), width, height))
+}
+
 #[tauri::command]
 fn copy_image_to_clipboard(image_data: Vec<u8>) -> Result<(), String> {
   if image_data.is_empty() {
     return Err("Image data was empty.".into());
   }
 
-  let image = image::load_from_memory(&image_data).map_err(|err| err.to_string())?;
-  let rgba = image.to_rgba8();
-  let (width, height) = image.dimensions();
+  let (rgba, width, height) = decode_to_rgba(&image_data)?;
 
   let mut clipboard = arboard::Clipboard::new().map_err(|err| err.to_string())?;
   clipboard
@@ -112,7 +176,7 @@ fn copy_image_to_clipboard(image_data: Vec<u8>) -> Result<(), String> {
 }
 
 #[tauri::command]
-fn move_to_trash(target_path: String) -> Result<(), String> {
+pub(crate) fn move_to_trash(target_path: String) -> Result<(), String> {
   let trimmed = target_path.trim();
   if trimmed.is_empty() {
     return Err("Target path was empty.".into());
@@ -127,7 +191,7 @@ fn move_to_trash(target_path: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-fn reveal_in_file_manager(target_path: String) -> Result<(), String> {
+pub(crate) fn reveal_in_file_manager(target_path: String) -> Result<(), String> {
   let trimmed = target_path.trim();
   if trimmed.is_empty() {
     return Err("Path was empty.".into());
@@ -159,18 +223,7 @@ fn reveal_in_file_manager(target_path: String) -> Result<(), String> {
 
 #[cfg(target_os = "macos")]
 fn reveal_on_macos(path: &Path) -> Result<(), String> {
-  std::process::Command::new("open")
-    .arg("-R")
-    .arg(path)
-    .status()
-    .map_err(|err| err.to_string())
-    .and_then(|status| {
-      if status.success() {
-        Ok(())
-      } else {
-        Err(format!("open -R exited with status: {status}"))
-      }
-    })
+  spawn::spawn_external(std::process::Command::new("open").arg("-R").arg(path))
 }
 
 #[cfg(target_os = "windows")]
@@ -178,33 +231,13 @@ fn reveal_on_windows(path: &Path) -> Result<(), String> {
   use std::ffi::OsString;
 
   if path.is_dir() {
-    return std::process::Command::new("explorer")
-      .arg(path)
-      .status()
-      .map_err(|err| err.to_string())
-      .and_then(|status| {
-        if status.success() {
-          Ok(())
-        } else {
-          Err(format!("explorer exited with status: {status}"))
-        }
-      });
+    return spawn::spawn_external(std::process::Command::new("explorer").arg(path));
   }
 
   let mut selector = OsString::from("/select,");
   selector.push(path);
 
-  std::process::Command::new("explorer")
-    .arg(selector)
-    .status()
-    .map_err(|err| err.to_string())
-    .and_then(|status| {
-      if status.success() {
-        Ok(())
-      } else {
-        Err(format!("explorer exited with status: {status}"))
-      }
-    })
+  spawn::spawn_external(std::process::Command::new("explorer").arg(selector))
 }
 
 #[cfg(all(unix, not(target_os = "macos")))]
@@ -218,17 +251,7 @@ fn reveal_on_unix(path: &Path) -> Result<(), String> {
       .unwrap_or_else(|| std::path::PathBuf::from("/"))
   };
 
-  std::process::Command::new("xdg-open")
-    .arg(folder)
-    .status()
-    .map_err(|err| err.to_string())
-    .and_then(|status| {
-      if status.success() {
-        Ok(())
-      } else {
-        Err(format!("xdg-open exited with status: {status}"))
-      }
-    })
+  spawn::spawn_external(std::process::Command::new("xdg-open").arg(folder))
 }
 
 fn main() {
@@ -237,7 +260,15 @@ fn main() {
       reveal_in_file_manager,
       copy_image_to_clipboard,
       move_to_trash,
-      get_file_metadata
+      get_file_metadata,
+      open_with::list_applications_for_file,
+      open_with::open_with,
+      batch::move_to_trash_many,
+      batch::get_file_metadata_many,
+      batch::reveal_in_file_manager_many,
+      archive::export_library,
+      archive::import_library,
+      thumbnail::get_thumbnail
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
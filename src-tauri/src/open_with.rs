@@ -0,0 +1,685 @@
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AppHandler {
+  pub name: String,
+  pub id: String,
+  pub icon: Option<String>,
+}
+
+#[tauri::command]
+pub fn list_applications_for_file(target_path: String) -> Result<Vec<AppHandler>, String> {
+  let trimmed = target_path.trim();
+  if trimmed.is_empty() {
+    return Err("Target path was empty.".into());
+  }
+
+  let path = Path::new(trimmed);
+  if !path.exists() {
+    return Err(format!("Path does not exist: {trimmed}"));
+  }
+
+  #[cfg(target_os = "macos")]
+  {
+    return macos::list_applications(path);
+  }
+
+  #[cfg(target_os = "windows")]
+  {
+    return windows::list_applications(path);
+  }
+
+  #[cfg(all(unix, not(target_os = "macos")))]
+  {
+    return linux::list_applications(path);
+  }
+
+  #[allow(unreachable_code)]
+  Err("Listing applications is not supported on this platform.".into())
+}
+
+#[tauri::command]
+pub fn open_with(app_id: String, target_paths: Vec<String>) -> Result<(), String> {
+  if target_paths.is_empty() {
+    return Err("No target paths were provided.".into());
+  }
+
+  #[cfg(target_os = "macos")]
+  {
+    return macos::open_with(&app_id, &target_paths);
+  }
+
+  #[cfg(target_os = "windows")]
+  {
+    return windows::open_with(&app_id, &target_paths);
+  }
+
+  #[cfg(all(unix, not(target_os = "macos")))]
+  {
+    return linux::open_with(&app_id, &target_paths);
+  }
+
+  #[allow(unreachable_code)]
+  Err("Opening with a specific application is not supported on this platform.".into())
+}
+
+fn sniff_mime_type(path: &Path) -> String {
+  let extension = path
+    .extension()
+    .and_then(|ext| ext.to_str())
+    .unwrap_or("")
+    .to_lowercase();
+
+  let by_extension = match extension.as_str() {
+    "png" => Some("image/png"),
+    "jpg" | "jpeg" => Some("image/jpeg"),
+    "gif" => Some("image/gif"),
+    "bmp" => Some("image/bmp"),
+    "webp" => Some("image/webp"),
+    "tiff" | "tif" => Some("image/tiff"),
+    "txt" | "plib" | "aoe" => Some("text/plain"),
+    "json" => Some("application/json"),
+    "pdf" => Some("application/pdf"),
+    _ => None,
+  };
+
+  if let Some(mime) = by_extension {
+    return mime.to_string();
+  }
+
+  match std::fs::read(path).ok().as_deref() {
+    Some(bytes) if bytes.starts_with(b"\x89PNG\r\n\x1a\n") => "image/png".into(),
+    Some(bytes) if bytes.starts_with(b"\xff\xd8\xff") => "image/jpeg".into(),
+    Some(bytes) if bytes.starts_with(b"GIF8") => "image/gif".into(),
+    Some(bytes) if bytes.starts_with(b"%PDF") => "application/pdf".into(),
+    Some(bytes) if bytes.iter().take(512).all(|byte| *byte != 0) => "text/plain".into(),
+    _ => "application/octet-stream".into(),
+  }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod linux {
+  use super::{sniff_mime_type, AppHandler};
+  use std::collections::HashSet;
+  use std::path::{Path, PathBuf};
+
+  pub fn list_applications(path: &Path) -> Result<Vec<AppHandler>, String> {
+    let mime = sniff_mime_type(path);
+    let associated_ids = associated_desktop_ids(&mime);
+
+    let mut handlers = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for dir in application_dirs() {
+      let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => continue,
+      };
+
+      for entry in entries.flatten() {
+        let entry_path = entry.path();
+        if entry_path.extension().and_then(|ext| ext.to_str()) != Some("desktop") {
+          continue;
+        }
+
+        let file_name = match entry_path.file_name().and_then(|name| name.to_str()) {
+          Some(name) => name.to_string(),
+          None => continue,
+        };
+
+        if !seen_ids.insert(file_name.clone()) {
+          continue;
+        }
+
+        let Some((handler, mime_types)) = parse_desktop_entry(&entry_path, &file_name) else {
+          continue;
+        };
+
+        let is_associated = associated_ids.contains(&file_name);
+        if !mime_types.contains(&mime) && !is_associated {
+          continue;
+        }
+
+        handlers.push((handler, is_associated));
+      }
+    }
+
+    // mimeapps.list associations (the user's default/preferred apps) sort ahead
+    // of apps that merely declare the mime type in their own .desktop entry.
+    handlers.sort_by(|(a, a_assoc), (b, b_assoc)| {
+      b_assoc
+        .cmp(a_assoc)
+        .then_with(|| a.name.cmp(&b.name))
+        .then_with(|| a.id.cmp(&b.id))
+    });
+
+    Ok(handlers.into_iter().map(|(handler, _)| handler).collect())
+  }
+
+  pub fn open_with(app_id: &str, target_paths: &[String]) -> Result<(), String> {
+    let entry_path = application_dirs()
+      .into_iter()
+      .map(|dir| dir.join(app_id))
+      .find(|candidate| candidate.is_file())
+      .ok_or_else(|| format!("No .desktop entry found for app id: {app_id}"))?;
+
+    let contents = std::fs::read_to_string(&entry_path).map_err(|err| err.to_string())?;
+    let exec = desktop_entry_value(&contents, "Exec")
+      .ok_or_else(|| format!("Desktop entry {app_id} has no Exec key."))?;
+
+    let argv = expand_exec(&exec, target_paths);
+    if argv.is_empty() {
+      return Err(format!("Desktop entry {app_id} has an empty Exec key."));
+    }
+
+    crate::spawn::spawn_external(std::process::Command::new(&argv[0]).args(&argv[1..]))
+  }
+
+  fn application_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Ok(data_home) = std::env::var("XDG_DATA_HOME") {
+      dirs.push(PathBuf::from(data_home).join("applications"));
+    } else if let Ok(home) = std::env::var("HOME") {
+      dirs.push(PathBuf::from(home).join(".local/share/applications"));
+    }
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+      .unwrap_or_else(|_| "/usr/local/share:/usr/share".to_string());
+    for dir in data_dirs.split(':').filter(|dir| !dir.is_empty()) {
+      dirs.push(PathBuf::from(dir).join("applications"));
+    }
+
+    dirs
+  }
+
+  fn mimeapps_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    if let Ok(config_home) = std::env::var("XDG_CONFIG_HOME") {
+      paths.push(PathBuf::from(config_home).join("mimeapps.list"));
+    } else if let Ok(home) = std::env::var("HOME") {
+      paths.push(PathBuf::from(&home).join(".config/mimeapps.list"));
+    }
+
+    for dir in application_dirs() {
+      paths.push(dir.join("mimeapps.list"));
+    }
+
+    paths
+  }
+
+  fn associated_desktop_ids(mime: &str) -> HashSet<String> {
+    let mut ids = HashSet::new();
+
+    for mimeapps in mimeapps_paths() {
+      let contents = match std::fs::read_to_string(&mimeapps) {
+        Ok(contents) => contents,
+        Err(_) => continue,
+      };
+
+      let mut in_associations = false;
+      for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+          in_associations = line == "[Default Applications]" || line == "[Added Associations]";
+          continue;
+        }
+
+        if !in_associations {
+          continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+          if key.trim() == mime {
+            ids.extend(value.split(';').filter(|id| !id.is_empty()).map(String::from));
+          }
+        }
+      }
+    }
+
+    ids
+  }
+
+  fn parse_desktop_entry(path: &Path, file_name: &str) -> Option<(AppHandler, HashSet<String>)> {
+    let contents = std::fs::read_to_string(path).ok()?;
+
+    if desktop_entry_value(&contents, "NoDisplay").as_deref() == Some("true") {
+      return None;
+    }
+    if desktop_entry_value(&contents, "Hidden").as_deref() == Some("true") {
+      return None;
+    }
+
+    let name = desktop_entry_value(&contents, "Name")?;
+    let icon = desktop_entry_value(&contents, "Icon");
+    let mime_types = desktop_entry_value(&contents, "MimeType")
+      .map(|value| value.split(';').filter(|mime| !mime.is_empty()).map(String::from).collect())
+      .unwrap_or_default();
+
+    Some((
+      AppHandler {
+        name,
+        id: file_name.to_string(),
+        icon,
+      },
+      mime_types,
+    ))
+  }
+
+  fn desktop_entry_value(contents: &str, key: &str) -> Option<String> {
+    let mut in_desktop_entry = false;
+    for line in contents.lines() {
+      let line = line.trim();
+      if line.starts_with('[') {
+        in_desktop_entry = line == "[Desktop Entry]";
+        continue;
+      }
+
+      if !in_desktop_entry {
+        continue;
+      }
+
+      if let Some((found_key, value)) = line.split_once('=') {
+        if found_key.trim() == key {
+          return Some(value.trim().to_string());
+        }
+      }
+    }
+
+    None
+  }
+
+  fn expand_exec(exec: &str, target_paths: &[String]) -> Vec<String> {
+    let mut argv = Vec::new();
+
+    for token in tokenize_exec(exec) {
+      match token.as_str() {
+        "%f" | "%u" => {
+          if let Some(first) = target_paths.first() {
+            argv.push(first.clone());
+          }
+        }
+        "%F" | "%U" => argv.extend(target_paths.iter().cloned()),
+        "%i" | "%c" | "%k" => {}
+        other => argv.push(other.to_string()),
+      }
+    }
+
+    argv
+  }
+
+  /// Tokenizes a `.desktop` `Exec` value per the Desktop Entry Specification's
+  /// quoting rules, so quoted paths containing spaces (e.g. AppImages under
+  /// `~/Applications/My App.AppImage`) stay as a single argv entry.
+  fn tokenize_exec(exec: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut chars = exec.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+      match ch {
+        c if c.is_whitespace() => {
+          if in_token {
+            tokens.push(std::mem::take(&mut current));
+            in_token = false;
+          }
+        }
+        '"' | '\'' => {
+          in_token = true;
+          let quote = ch;
+          while let Some(&next) = chars.peek() {
+            if next == quote {
+              chars.next();
+              break;
+            }
+            if next == '\\' {
+              chars.next();
+              if let Some(escaped) = chars.next() {
+                current.push(escaped);
+              }
+            } else {
+              current.push(next);
+              chars.next();
+            }
+          }
+        }
+        '\\' => {
+          in_token = true;
+          if let Some(escaped) = chars.next() {
+            current.push(escaped);
+          }
+        }
+        other => {
+          in_token = true;
+          current.push(other);
+        }
+      }
+    }
+
+    if in_token {
+      tokens.push(current);
+    }
+
+    tokens
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    #[test]
+    fn desktop_entry_value_reads_the_desktop_entry_group() {
+      let contents = "[Desktop Entry]\nName=GIMP\nMimeType=image/png;image/jpeg;\n\n[Desktop Action new-window]\nName=Open a New Window\n";
+      assert_eq!(desktop_entry_value(contents, "Name"), Some("GIMP".to_string()));
+      assert_eq!(
+        desktop_entry_value(contents, "MimeType"),
+        Some("image/png;image/jpeg;".to_string())
+      );
+    }
+
+    #[test]
+    fn tokenize_exec_splits_on_whitespace() {
+      assert_eq!(
+        tokenize_exec("gimp %U"),
+        vec!["gimp".to_string(), "%U".to_string()]
+      );
+    }
+
+    #[test]
+    fn tokenize_exec_keeps_quoted_paths_with_spaces_as_one_token() {
+      assert_eq!(
+        tokenize_exec("\"/home/user/Applications/My App.AppImage\" %f"),
+        vec![
+          "/home/user/Applications/My App.AppImage".to_string(),
+          "%f".to_string()
+        ]
+      );
+    }
+
+    #[test]
+    fn expand_exec_expands_field_codes() {
+      let paths = vec!["/tmp/a.png".to_string(), "/tmp/b.png".to_string()];
+      assert_eq!(
+        expand_exec("viewer %F", &paths),
+        vec!["viewer".to_string(), "/tmp/a.png".to_string(), "/tmp/b.png".to_string()]
+      );
+      assert_eq!(
+        expand_exec("viewer %f", &paths),
+        vec!["viewer".to_string(), "/tmp/a.png".to_string()]
+      );
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+  use super::AppHandler;
+  use std::path::{Path, PathBuf};
+
+  pub fn list_applications(_path: &Path) -> Result<Vec<AppHandler>, String> {
+    let mut handlers = Vec::new();
+
+    for dir in [PathBuf::from("/Applications"), PathBuf::from("/System/Applications")] {
+      let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => continue,
+      };
+
+      for entry in entries.flatten() {
+        let entry_path = entry.path();
+        if entry_path.extension().and_then(|ext| ext.to_str()) != Some("app") {
+          continue;
+        }
+
+        let name = entry_path
+          .file_stem()
+          .and_then(|stem| stem.to_str())
+          .unwrap_or_default()
+          .to_string();
+
+        handlers.push(AppHandler {
+          name,
+          id: entry_path.to_string_lossy().to_string(),
+          icon: None,
+        });
+      }
+    }
+
+    handlers.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+    Ok(handlers)
+  }
+
+  pub fn open_with(app_id: &str, target_paths: &[String]) -> Result<(), String> {
+    crate::spawn::spawn_external(
+      std::process::Command::new("open")
+        .arg("-a")
+        .arg(app_id)
+        .args(target_paths),
+    )
+  }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+  use super::AppHandler;
+  use std::collections::HashSet;
+  use std::path::Path;
+  use winreg::enums::{HKEY_CLASSES_ROOT, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+  use winreg::RegKey;
+
+  pub fn list_applications(path: &Path) -> Result<Vec<AppHandler>, String> {
+    let extension = path
+      .extension()
+      .and_then(|ext| ext.to_str())
+      .map(|ext| format!(".{}", ext.to_lowercase()))
+      .ok_or_else(|| "Path has no extension to look up handlers for.".to_string())?;
+
+    let mut handlers = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    // The per-extension "Open With" MRU only covers apps the user has
+    // manually picked before and is absent on a fresh install, so it is
+    // just one of several sources rather than the only one.
+    let file_exts_key =
+      format!("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\FileExts\\{extension}");
+
+    if let Ok(open_with_list) =
+      RegKey::predef(HKEY_CURRENT_USER).open_subkey(format!("{file_exts_key}\\OpenWithList"))
+    {
+      for (value_name, _) in open_with_list.enum_values().filter_map(|entry| entry.ok()) {
+        if value_name == "MRUList" {
+          continue;
+        }
+        // Values are named `a`, `b`, ...; each one's data is the executable name.
+        let Ok(exe_name) = open_with_list.get_value::<String, _>(&value_name) else {
+          continue;
+        };
+        if !seen_ids.insert(exe_name.clone()) {
+          continue;
+        }
+
+        let name = Path::new(&exe_name)
+          .file_stem()
+          .and_then(|stem| stem.to_str())
+          .unwrap_or(&exe_name)
+          .to_string();
+
+        handlers.push(AppHandler {
+          name,
+          id: resolve_app_path(&exe_name).unwrap_or_else(|| exe_name.clone()),
+          icon: None,
+        });
+      }
+    }
+
+    // ProgIDs explicitly registered to open this extension.
+    if let Ok(open_with_progids) =
+      RegKey::predef(HKEY_CURRENT_USER).open_subkey(format!("{file_exts_key}\\OpenWithProgids"))
+    {
+      for (progid, _) in open_with_progids.enum_values().filter_map(|entry| entry.ok()) {
+        if seen_ids.insert(progid.clone()) {
+          if let Some(handler) = resolve_progid_handler(&progid) {
+            handlers.push(handler);
+          }
+        }
+      }
+    }
+
+    // The extension's own default handler, e.g. `HKCR\.png` -> a ProgID.
+    if let Some(default_progid) = RegKey::predef(HKEY_CLASSES_ROOT)
+      .open_subkey(&extension)
+      .ok()
+      .and_then(|key| key.get_value::<String, _>("").ok())
+      .filter(|progid: &String| !progid.is_empty())
+    {
+      if seen_ids.insert(default_progid.clone()) {
+        if let Some(handler) = resolve_progid_handler(&default_progid) {
+          handlers.push(handler);
+        }
+      }
+    }
+
+    handlers.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+    Ok(handlers)
+  }
+
+  fn resolve_app_path(exe_name: &str) -> Option<String> {
+    let key_path = format!("Software\\Microsoft\\Windows\\CurrentVersion\\App Paths\\{exe_name}");
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+      .open_subkey(key_path)
+      .ok()?
+      .get_value::<String, _>("")
+      .ok()
+  }
+
+  fn resolve_progid_handler(progid: &str) -> Option<AppHandler> {
+    let progid_key = RegKey::predef(HKEY_CLASSES_ROOT).open_subkey(progid).ok()?;
+
+    let name = progid_key
+      .get_value::<String, _>("FriendlyTypeName")
+      .ok()
+      .or_else(|| progid_key.get_value::<String, _>("").ok())
+      .filter(|name: &String| !name.is_empty())
+      .unwrap_or_else(|| progid.to_string());
+
+    let command = progid_key
+      .open_subkey("shell\\open\\command")
+      .ok()?
+      .get_value::<String, _>("")
+      .ok()?;
+
+    Some(AppHandler {
+      name,
+      id: command,
+      icon: None,
+    })
+  }
+
+  pub fn open_with(app_id: &str, target_paths: &[String]) -> Result<(), String> {
+    let argv = expand_command(app_id, target_paths);
+    if argv.is_empty() {
+      return Err("Resolved handler has no command to run.".into());
+    }
+
+    crate::spawn::spawn_external(std::process::Command::new(&argv[0]).args(&argv[1..]))
+  }
+
+  /// Expands a resolved handler command (either a bare executable path from
+  /// `OpenWithList`/`App Paths`, or a `shell\open\command` string such as
+  /// `"C:\Program Files\App\app.exe" "%1"`) into an argv.
+  fn expand_command(command: &str, target_paths: &[String]) -> Vec<String> {
+    let tokens = tokenize_command(command);
+    let has_placeholder = tokens
+      .iter()
+      .any(|token| matches!(token.as_str(), "%1" | "%L" | "%*"));
+
+    let mut argv = Vec::new();
+    for token in &tokens {
+      match token.as_str() {
+        "%1" | "%L" => {
+          if let Some(first) = target_paths.first() {
+            argv.push(first.clone());
+          }
+        }
+        "%*" => argv.extend(target_paths.iter().cloned()),
+        other => argv.push(other.to_string()),
+      }
+    }
+
+    if !has_placeholder {
+      argv.extend(target_paths.iter().cloned());
+    }
+
+    argv
+  }
+
+  /// Splits a command line on whitespace, honoring double-quoted segments
+  /// (e.g. a quoted path containing spaces); backslashes are left literal
+  /// since they are ordinary path separators on Windows, not escapes.
+  fn tokenize_command(command: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+      match ch {
+        c if c.is_whitespace() => {
+          if in_token {
+            tokens.push(std::mem::take(&mut current));
+            in_token = false;
+          }
+        }
+        '"' => {
+          in_token = true;
+          for next in chars.by_ref() {
+            if next == '"' {
+              break;
+            }
+            current.push(next);
+          }
+        }
+        other => {
+          in_token = true;
+          current.push(other);
+        }
+      }
+    }
+
+    if in_token {
+      tokens.push(current);
+    }
+
+    tokens
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_command_substitutes_percent_one() {
+      let paths = vec!["C:\\Users\\me\\a.png".to_string()];
+      assert_eq!(
+        expand_command("\"C:\\Program Files\\App\\app.exe\" \"%1\"", &paths),
+        vec![
+          "C:\\Program Files\\App\\app.exe".to_string(),
+          "C:\\Users\\me\\a.png".to_string()
+        ]
+      );
+    }
+
+    #[test]
+    fn expand_command_appends_paths_when_no_placeholder() {
+      let paths = vec!["C:\\Users\\me\\a.png".to_string()];
+      assert_eq!(
+        expand_command("notepad.exe", &paths),
+        vec!["notepad.exe".to_string(), "C:\\Users\\me\\a.png".to_string()]
+      );
+    }
+  }
+}
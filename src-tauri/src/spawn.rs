@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+/// Variables whose values are colon-separated path lists that bundle runtimes
+/// (AppImage/Flatpak/Snap) rewrite, and that must be cleaned before they leak
+/// into externally launched processes.
+const PATH_LIST_VARS: &[&str] = &[
+  "PATH",
+  "LD_LIBRARY_PATH",
+  "XDG_DATA_DIRS",
+  "GST_PLUGIN_SYSTEM_PATH",
+  "GIO_MODULE_DIR",
+];
+
+/// Spawns `command` after stripping bundle-injected environment variables so
+/// the launched process behaves as if it were started outside the sandbox.
+pub fn spawn_external(command: &mut Command) -> Result<(), String> {
+  for (key, value) in cleaned_env() {
+    command.env(key, value);
+  }
+  for key in removed_env() {
+    command.env_remove(key);
+  }
+
+  command
+    .status()
+    .map_err(|err| err.to_string())
+    .and_then(|status| {
+      if status.success() {
+        Ok(())
+      } else {
+        Err(format!("command exited with status: {status}"))
+      }
+    })
+}
+
+fn bundle_mount_prefixes() -> Vec<String> {
+  let mut prefixes = Vec::new();
+
+  if Path::new("/.flatpak-info").exists() {
+    if let Ok(mount) = std::env::var("FLATPAK_SANDBOX_DIR") {
+      prefixes.push(mount);
+    }
+    prefixes.push("/app".to_string());
+  }
+
+  if let Ok(snap) = std::env::var("SNAP") {
+    prefixes.push(snap);
+  }
+
+  if let Ok(appdir) = std::env::var("APPDIR") {
+    prefixes.push(appdir);
+  }
+  if std::env::var("APPIMAGE").is_ok() {
+    prefixes.push("/tmp/.mount_".to_string());
+  }
+
+  prefixes
+}
+
+fn is_bundle_path(entry: &str, prefixes: &[String]) -> bool {
+  prefixes.iter().any(|prefix| entry.starts_with(prefix.as_str()))
+}
+
+fn clean_path_list(var: &str, prefixes: &[String]) -> Option<String> {
+  // Only trust a launcher-saved backup when we've actually detected a bundle
+  // runtime; otherwise an unrelated `FOO_OLD` in the user's own shell would
+  // get substituted in for `FOO` on every external spawn.
+  if !prefixes.is_empty() {
+    if let Ok(backup) = std::env::var(format!("{var}_OLD")) {
+      if !backup.is_empty() {
+        return Some(backup);
+      }
+    }
+    if let Ok(backup) = std::env::var(format!("BACKUP_{var}")) {
+      if !backup.is_empty() {
+        return Some(backup);
+      }
+    }
+  }
+
+  let current = std::env::var(var).ok()?;
+
+  let mut seen = HashSet::new();
+  let mut cleaned = Vec::new();
+  for entry in current.split(':') {
+    if entry.is_empty() || is_bundle_path(entry, prefixes) {
+      continue;
+    }
+    if seen.insert(entry.to_string()) {
+      cleaned.push(entry.to_string());
+    }
+  }
+
+  Some(cleaned.join(":"))
+}
+
+fn cleaned_env() -> Vec<(String, String)> {
+  let prefixes = bundle_mount_prefixes();
+
+  PATH_LIST_VARS
+    .iter()
+    .filter_map(|var| clean_path_list(var, &prefixes).map(|value| (var.to_string(), value)))
+    .filter(|(_, value)| !value.is_empty())
+    .collect()
+}
+
+fn removed_env() -> Vec<String> {
+  let prefixes = bundle_mount_prefixes();
+
+  PATH_LIST_VARS
+    .iter()
+    .filter(|var| matches!(clean_path_list(var, &prefixes), Some(value) if value.is_empty()))
+    .map(|var| var.to_string())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn clean_path_list_drops_bundle_mount_entries() {
+    std::env::set_var("SPAWN_TEST_VAR_1", "/app/bin:/usr/bin:/usr/local/bin");
+    let cleaned = clean_path_list("SPAWN_TEST_VAR_1", &["/app".to_string()]);
+    std::env::remove_var("SPAWN_TEST_VAR_1");
+
+    assert_eq!(cleaned, Some("/usr/bin:/usr/local/bin".to_string()));
+  }
+
+  #[test]
+  fn clean_path_list_ignores_backup_var_outside_a_bundle() {
+    std::env::set_var("SPAWN_TEST_VAR_2", "/usr/bin");
+    std::env::set_var("SPAWN_TEST_VAR_2_OLD", "/some/unrelated/leftover");
+
+    let cleaned = clean_path_list("SPAWN_TEST_VAR_2", &[]);
+
+    std::env::remove_var("SPAWN_TEST_VAR_2");
+    std::env::remove_var("SPAWN_TEST_VAR_2_OLD");
+
+    assert_eq!(cleaned, Some("/usr/bin".to_string()));
+  }
+
+  #[test]
+  fn clean_path_list_restores_backup_var_inside_a_bundle() {
+    std::env::set_var("SPAWN_TEST_VAR_3", "/app/bin");
+    std::env::set_var("SPAWN_TEST_VAR_3_OLD", "/usr/bin:/usr/local/bin");
+
+    let cleaned = clean_path_list("SPAWN_TEST_VAR_3", &["/app".to_string()]);
+
+    std::env::remove_var("SPAWN_TEST_VAR_3");
+    std::env::remove_var("SPAWN_TEST_VAR_3_OLD");
+
+    assert_eq!(cleaned, Some("/usr/bin:/usr/local/bin".to_string()));
+  }
+}
@@ -0,0 +1,112 @@
+use base64::Engine;
+use image::GenericImageView;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::Cursor;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailResult {
+  data_url: String,
+  original_width: u32,
+  original_height: u32,
+  width: u32,
+  height: u32,
+}
+
+#[tauri::command]
+pub fn get_thumbnail(
+  app_handle: tauri::AppHandle,
+  target_path: String,
+  max_edge: u32,
+) -> Result<ThumbnailResult, String> {
+  let trimmed = target_path.trim();
+  if trimmed.is_empty() {
+    return Err("Target path was empty.".into());
+  }
+  if max_edge == 0 {
+    return Err("max_edge must be greater than zero.".into());
+  }
+
+  let path = Path::new(trimmed);
+  if !path.exists() {
+    return Err(format!("Path does not exist: {trimmed}"));
+  }
+
+  let metadata = std::fs::metadata(path).map_err(|err| err.to_string())?;
+  let mtime_ms = metadata
+    .modified()
+    .ok()
+    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+    .map(|duration| duration.as_millis())
+    .unwrap_or(0);
+
+  let (original_width, original_height) =
+    image::image_dimensions(path).map_err(|err| err.to_string())?;
+
+  let cache_dir = app_handle
+    .path_resolver()
+    .app_cache_dir()
+    .ok_or_else(|| "Could not resolve the app cache directory.".to_string())?
+    .join("thumbnails");
+  std::fs::create_dir_all(&cache_dir).map_err(|err| err.to_string())?;
+
+  let cache_path = cache_dir.join(format!(
+    "{}.png",
+    cache_key(path, mtime_ms, max_edge)
+  ));
+
+  if let Ok(cached_bytes) = std::fs::read(&cache_path) {
+    if let Ok((width, height)) = image::load_from_memory(&cached_bytes)
+      .map(|image| image.dimensions())
+    {
+      return Ok(ThumbnailResult {
+        data_url: to_data_url(&cached_bytes),
+        original_width,
+        original_height,
+        width,
+        height,
+      });
+    }
+  }
+
+  let source_bytes = std::fs::read(path).map_err(|err| err.to_string())?;
+  let (source_rgba, _, _) = crate::decode_to_rgba(&source_bytes)?;
+
+  let image = image::DynamicImage::ImageRgba8(source_rgba);
+  let thumbnail = image.resize(max_edge, max_edge, image::imageops::FilterType::Lanczos3);
+  let (width, height) = thumbnail.dimensions();
+
+  let mut bytes = Vec::new();
+  thumbnail
+    .write_to(&mut Cursor::new(&mut bytes), image::ImageOutputFormat::Png)
+    .map_err(|err| err.to_string())?;
+
+  std::fs::write(&cache_path, &bytes).map_err(|err| err.to_string())?;
+
+  Ok(ThumbnailResult {
+    data_url: to_data_url(&bytes),
+    original_width,
+    original_height,
+    width,
+    height,
+  })
+}
+
+fn cache_key(path: &Path, mtime_ms: u128, max_edge: u32) -> String {
+  let mut hasher = DefaultHasher::new();
+  path.to_string_lossy().hash(&mut hasher);
+  mtime_ms.hash(&mut hasher);
+  max_edge.hash(&mut hasher);
+  format!("{:x}", hasher.finish())
+}
+
+fn to_data_url(bytes: &[u8]) -> String {
+  format!(
+    "data:image/png;base64,{}",
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+  )
+}